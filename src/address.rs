@@ -0,0 +1,348 @@
+//! Network-aware Bitcoin addresses: Base58Check P2PKH/P2SH and
+//! Bech32/Bech32m SegWit addresses, mirroring the checked/unchecked address
+//! split rust-bitcoin uses to keep a parsed address from being spent on the
+//! wrong network.
+
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::hashes::sha256d;
+use crate::segwit::WitnessProgram;
+use crate::BitcoinError;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Which Bitcoin network an address belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Regtest,
+    Signet,
+}
+
+/// What a base58/bech32 prefix tells us about the network, which for some
+/// prefixes is less than a single concrete network: testnet, regtest and
+/// signet all share the same Base58Check version bytes, and bech32's `tb`
+/// human-readable part is shared by testnet and signet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetworkGuess {
+    Exact(Network),
+    NonMainnet,
+    TestnetOrSignet,
+}
+
+impl NetworkGuess {
+    fn accepts(self, required: Network) -> bool {
+        match self {
+            NetworkGuess::Exact(network) => network == required,
+            NetworkGuess::NonMainnet => required != Network::Mainnet,
+            NetworkGuess::TestnetOrSignet => matches!(required, Network::Testnet | Network::Signet),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Payload {
+    PubkeyHash([u8; 20]),
+    ScriptHash([u8; 20]),
+    Witness(WitnessProgram),
+}
+
+/// Marker for an `Address` whose network has not been checked against the
+/// caller's expectation yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkUnchecked;
+
+/// Marker for an `Address` confirmed to belong to a specific `Network`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkChecked;
+
+/// A parsed Bitcoin address. Defaults to the network-checked form; parse via
+/// `"...".parse::<Address<NetworkUnchecked>>()` and call `require_network`
+/// to validate it against the network you expect, the same split
+/// rust-bitcoin uses to stop a testnet address from being paid on mainnet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address<V = NetworkChecked> {
+    payload: Payload,
+    network: NetworkGuess,
+    _checked: PhantomData<V>,
+}
+
+impl Address<NetworkUnchecked> {
+    /// Confirms this address belongs to `required`, returning a checked
+    /// `Address` or `BitcoinError::ParseError` on a network mismatch.
+    pub fn require_network(self, required: Network) -> Result<Address<NetworkChecked>, BitcoinError> {
+        if self.network.accepts(required) {
+            Ok(Address {
+                payload: self.payload,
+                network: NetworkGuess::Exact(required),
+                _checked: PhantomData,
+            })
+        } else {
+            Err(BitcoinError::ParseError(format!(
+                "address does not belong to {required:?}"
+            )))
+        }
+    }
+
+    fn parse_base58(s: &str) -> Result<Self, BitcoinError> {
+        let payload = base58check_decode(s)?;
+        let (&version, hash) = payload
+            .split_first()
+            .ok_or_else(|| BitcoinError::ParseError("empty base58 payload".to_string()))?;
+
+        if hash.len() != 20 {
+            return Err(BitcoinError::ParseError(format!(
+                "unexpected base58 payload length {}",
+                hash.len()
+            )));
+        }
+        let mut bytes = [0u8; 20];
+        bytes.copy_from_slice(hash);
+
+        let (payload, network) = match version {
+            0x00 => (Payload::PubkeyHash(bytes), NetworkGuess::Exact(Network::Mainnet)),
+            0x05 => (Payload::ScriptHash(bytes), NetworkGuess::Exact(Network::Mainnet)),
+            0x6f => (Payload::PubkeyHash(bytes), NetworkGuess::NonMainnet),
+            0xc4 => (Payload::ScriptHash(bytes), NetworkGuess::NonMainnet),
+            other => {
+                return Err(BitcoinError::ParseError(format!(
+                    "unknown base58 version byte 0x{other:02x}"
+                )));
+            },
+        };
+
+        Ok(Self { payload, network, _checked: PhantomData })
+    }
+
+    fn parse_bech32(s: &str) -> Result<Self, BitcoinError> {
+        let (hrp, data, encoding) = bech32_decode(s)?;
+
+        let network = match hrp.as_str() {
+            "bc" => NetworkGuess::Exact(Network::Mainnet),
+            "tb" => NetworkGuess::TestnetOrSignet,
+            "bcrt" => NetworkGuess::Exact(Network::Regtest),
+            other => return Err(BitcoinError::ParseError(format!("unknown bech32 HRP '{other}'"))),
+        };
+
+        let (&version, groups) = data
+            .split_first()
+            .ok_or_else(|| BitcoinError::ParseError("empty bech32 data".to_string()))?;
+        let program = convert_bits(groups, 5, 8, false)?;
+
+        match version {
+            0 if encoding == Bech32Encoding::Bech32 && matches!(program.len(), 20 | 32) => {},
+            0 => {
+                return Err(BitcoinError::ParseError(
+                    "v0 witness programs must be bech32-encoded with a 20 or 32 byte program"
+                        .to_string(),
+                ));
+            },
+            1..=16 if encoding == Bech32Encoding::Bech32m && (1..=40).contains(&program.len()) => {},
+            1..=16 => {
+                return Err(BitcoinError::ParseError(
+                    "v1+ witness programs must be bech32m-encoded with a 1-40 byte program"
+                        .to_string(),
+                ));
+            },
+            other => return Err(BitcoinError::ParseError(format!("invalid witness version {other}"))),
+        }
+
+        Ok(Self {
+            payload: Payload::Witness(WitnessProgram::new(version, program)),
+            network,
+            _checked: PhantomData,
+        })
+    }
+}
+
+impl FromStr for Address<NetworkUnchecked> {
+    type Err = BitcoinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        if lower.starts_with("bc1") || lower.starts_with("tb1") || lower.starts_with("bcrt1") {
+            Self::parse_bech32(s)
+        } else {
+            Self::parse_base58(s)
+        }
+    }
+}
+
+impl Address<NetworkChecked> {
+    /// Builds the `scriptPubKey` that pays to this address.
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        match &self.payload {
+            Payload::PubkeyHash(hash) => {
+                let mut script = Vec::with_capacity(25);
+                script.push(0x76); // OP_DUP
+                script.push(0xa9); // OP_HASH160
+                script.push(0x14); // push 20 bytes
+                script.extend_from_slice(hash);
+                script.push(0x88); // OP_EQUALVERIFY
+                script.push(0xac); // OP_CHECKSIG
+                script
+            },
+            Payload::ScriptHash(hash) => {
+                let mut script = Vec::with_capacity(23);
+                script.push(0xa9); // OP_HASH160
+                script.push(0x14); // push 20 bytes
+                script.extend_from_slice(hash);
+                script.push(0x87); // OP_EQUAL
+                script
+            },
+            Payload::Witness(program) => {
+                let mut script = Vec::with_capacity(2 + program.program.len());
+                script.push(witness_version_opcode(program.version));
+                script.push(program.program.len() as u8);
+                script.extend_from_slice(&program.program);
+                script
+            },
+        }
+    }
+}
+
+fn witness_version_opcode(version: u8) -> u8 {
+    if version == 0 { 0x00 } else { 0x50 + version }
+}
+
+fn base58_decode(s: &str) -> Result<Vec<u8>, BitcoinError> {
+    let mut result: Vec<u8> = vec![0];
+    for c in s.chars() {
+        let digit = BASE58_ALPHABET
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or_else(|| BitcoinError::ParseError(format!("invalid base58 character '{c}'")))?
+            as u32;
+
+        let mut carry = digit;
+        for byte in result.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            result.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    for c in s.chars() {
+        if c == '1' { result.push(0) } else { break }
+    }
+    result.reverse();
+    Ok(result)
+}
+
+fn base58check_decode(s: &str) -> Result<Vec<u8>, BitcoinError> {
+    let data = base58_decode(s)?;
+    if data.len() < 5 {
+        return Err(BitcoinError::ParseError("base58check payload too short".to_string()));
+    }
+
+    let (payload, checksum) = data.split_at(data.len() - 4);
+    if sha256d(payload)[..4] != *checksum {
+        return Err(BitcoinError::ParseError("invalid base58check checksum".to_string()));
+    }
+    Ok(payload.to_vec())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bech32Encoding {
+    Bech32,
+    Bech32m,
+}
+
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ff_ffff) << 5) ^ v as u32;
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn bech32_hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut v: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    v.push(0);
+    v.extend(hrp.bytes().map(|b| b & 31));
+    v
+}
+
+fn bech32_decode(s: &str) -> Result<(String, Vec<u8>, Bech32Encoding), BitcoinError> {
+    let err = |msg: &str| BitcoinError::ParseError(msg.to_string());
+
+    if s.len() < 8 || s.len() > 90 {
+        return Err(err("bech32 string has an invalid length"));
+    }
+    if s != s.to_lowercase() && s != s.to_uppercase() {
+        return Err(err("bech32 string has mixed case"));
+    }
+    let s = s.to_lowercase();
+
+    let sep = s.rfind('1').ok_or_else(|| err("bech32 string is missing the '1' separator"))?;
+    let (hrp, data_part) = (&s[..sep], &s[sep + 1..]);
+    if hrp.is_empty() || data_part.len() < 6 {
+        return Err(err("bech32 string has an empty HRP or data part"));
+    }
+
+    let mut data = Vec::with_capacity(data_part.len());
+    for c in data_part.chars() {
+        let v = BECH32_CHARSET
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or_else(|| err("invalid bech32 data character"))? as u8;
+        data.push(v);
+    }
+
+    let mut checksum_input = bech32_hrp_expand(hrp);
+    checksum_input.extend_from_slice(&data);
+    let encoding = match bech32_polymod(&checksum_input) {
+        BECH32_CONST => Bech32Encoding::Bech32,
+        BECH32M_CONST => Bech32Encoding::Bech32m,
+        _ => return Err(err("invalid bech32 checksum")),
+    };
+
+    data.truncate(data.len() - 6);
+    Ok((hrp.to_string(), data, encoding))
+}
+
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, BitcoinError> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut ret = Vec::new();
+    let maxv = (1u32 << to) - 1;
+
+    for &value in data {
+        if (value as u32) >> from != 0 {
+            return Err(BitcoinError::ParseError("bech32 data value out of range".to_string()));
+        }
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            ret.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to - bits)) & maxv) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & maxv) != 0 {
+        return Err(BitcoinError::ParseError("bech32 data has invalid padding".to_string()));
+    }
+
+    Ok(ret)
+}
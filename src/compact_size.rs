@@ -0,0 +1,69 @@
+//! CompactSize ("varint") encoding, as used throughout Bitcoin's consensus
+//! wire format to prefix variable-length lists and byte strings.
+
+use crate::BitcoinError;
+
+/// Encodes `value` as a CompactSize and appends it to `out`.
+pub fn write_compact_size(out: &mut Vec<u8>, value: u64) {
+    if value < 0xFD {
+        out.push(value as u8);
+    } else if value <= 0xFFFF {
+        out.push(0xFD);
+        out.extend_from_slice(&(value as u16).to_le_bytes());
+    } else if value <= 0xFFFF_FFFF {
+        out.push(0xFE);
+        out.extend_from_slice(&(value as u32).to_le_bytes());
+    } else {
+        out.push(0xFF);
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// Reads a CompactSize-encoded integer from `data` starting at `offset`.
+///
+/// Returns the decoded value together with the number of bytes consumed.
+/// Non-minimal encodings (e.g. a `0xFD` prefix for a value `< 0xFD`) and
+/// truncated buffers are rejected with `BitcoinError::InvalidTransaction`.
+pub fn read_compact_size(data: &[u8], offset: usize) -> Result<(u64, usize), BitcoinError> {
+    let prefix = *data.get(offset).ok_or(BitcoinError::InvalidTransaction)?;
+
+    match prefix {
+        0..=0xFC => Ok((prefix as u64, 1)),
+        0xFD => {
+            let bytes: [u8; 2] = data
+                .get(offset + 1..offset + 3)
+                .ok_or(BitcoinError::InvalidTransaction)?
+                .try_into()
+                .unwrap();
+            let value = u16::from_le_bytes(bytes) as u64;
+            if value < 0xFD {
+                return Err(BitcoinError::InvalidTransaction);
+            }
+            Ok((value, 3))
+        },
+        0xFE => {
+            let bytes: [u8; 4] = data
+                .get(offset + 1..offset + 5)
+                .ok_or(BitcoinError::InvalidTransaction)?
+                .try_into()
+                .unwrap();
+            let value = u32::from_le_bytes(bytes) as u64;
+            if value <= 0xFFFF {
+                return Err(BitcoinError::InvalidTransaction);
+            }
+            Ok((value, 5))
+        },
+        0xFF => {
+            let bytes: [u8; 8] = data
+                .get(offset + 1..offset + 9)
+                .ok_or(BitcoinError::InvalidTransaction)?
+                .try_into()
+                .unwrap();
+            let value = u64::from_le_bytes(bytes);
+            if value <= 0xFFFF_FFFF {
+                return Err(BitcoinError::InvalidTransaction);
+            }
+            Ok((value, 9))
+        },
+    }
+}
@@ -0,0 +1,96 @@
+//! CSV import/export of wallet UTXOs (`OutPoint` + `TxOutput` pairs), the
+//! way the older parity-bitcoin tooling used the `csv` crate for tabular
+//! data, so a wallet's UTXO set can round-trip through a spreadsheet.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BitcoinError, OutPoint, TxOutput};
+
+/// One UTXO: the outpoint it was paid to, and the `TxOutput` it locked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utxo {
+    pub outpoint: OutPoint,
+    pub output:   TxOutput,
+}
+
+#[derive(Serialize, Deserialize)]
+struct UtxoRecord {
+    txid:              String,
+    vout:              u32,
+    value:             u64,
+    script_pubkey_hex: String,
+}
+
+/// Serializes `utxos` as `txid,vout,value,script_pubkey_hex` CSV rows.
+pub fn export_utxos(utxos: &[Utxo]) -> Result<String, BitcoinError> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+
+    for utxo in utxos {
+        writer
+            .serialize(UtxoRecord {
+                txid:              hex_encode(&utxo.outpoint.txid),
+                vout:              utxo.outpoint.vout,
+                value:             utxo.output.value,
+                script_pubkey_hex: hex_encode(&utxo.output.script_pubkey),
+            })
+            .map_err(|e| BitcoinError::ParseError(format!("failed to write CSV row: {e}")))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| BitcoinError::ParseError(format!("failed to flush CSV writer: {e}")))?;
+    String::from_utf8(bytes)
+        .map_err(|e| BitcoinError::ParseError(format!("CSV output was not valid UTF-8: {e}")))
+}
+
+/// Parses CSV produced by `export_utxos` back into `Utxo`s, surfacing a
+/// malformed row as `BitcoinError::ParseError` naming its line number.
+pub fn import_utxos(csv_data: &str) -> Result<Vec<Utxo>, BitcoinError> {
+    let mut reader = ::csv::Reader::from_reader(csv_data.as_bytes());
+    let mut utxos = Vec::new();
+
+    for (i, record) in reader.deserialize::<UtxoRecord>().enumerate() {
+        // +2: one for the 1-indexed line number, one for the header row.
+        let line_no = i + 2;
+        let record = record.map_err(|e| BitcoinError::ParseError(format!("line {line_no}: {e}")))?;
+
+        let txid_bytes = hex_decode(&record.txid).map_err(|_| {
+            BitcoinError::ParseError(format!("line {line_no}: invalid txid hex '{}'", record.txid))
+        })?;
+        let txid: [u8; 32] = txid_bytes.try_into().map_err(|_| {
+            BitcoinError::ParseError(format!("line {line_no}: txid must be 32 bytes"))
+        })?;
+
+        let script_pubkey = hex_decode(&record.script_pubkey_hex).map_err(|_| {
+            BitcoinError::ParseError(format!(
+                "line {line_no}: invalid script_pubkey hex '{}'",
+                record.script_pubkey_hex
+            ))
+        })?;
+
+        utxos.push(Utxo {
+            outpoint: OutPoint { txid, vout: record.vout },
+            output:   TxOutput { value: record.value, script_pubkey },
+        });
+    }
+
+    Ok(utxos)
+}
+
+fn hex_encode(bytes: &[u8]) -> String { bytes.iter().map(|b| format!("{b:02x}")).collect() }
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, BitcoinError> {
+    if !s.len().is_multiple_of(2) {
+        return Err(BitcoinError::ParseError("hex string has odd length".to_string()));
+    }
+    if !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(BitcoinError::ParseError("hex string has a non-hex character".to_string()));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| BitcoinError::ParseError(format!("invalid hex byte '{}'", &s[i..i + 2])))
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+//! Bitcoin's double-SHA256 hashing, used for transaction ids, block hashes
+//! and merkle tree nodes.
+
+use sha2::{Digest, Sha256};
+
+/// Applies SHA-256 twice, the way Bitcoin hashes transactions, blocks and
+/// merkle nodes.
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    let first = Sha256::digest(data);
+    Sha256::digest(first).into()
+}
@@ -0,0 +1,192 @@
+//! secp256k1 keys and the curve arithmetic needed to build Taproot
+//! (BIP340/341) outputs: compressed and x-only public keys, secret keys,
+//! and the even-Y-coordinate normalization Taproot output keys require.
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::segwit::WitnessProgram;
+use crate::BitcoinError;
+
+/// A point on the secp256k1 curve in affine coordinates, or the point at
+/// infinity (the curve's additive identity).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurvePoint {
+    Infinity,
+    Affine { x: BigUint, y: BigUint },
+}
+
+/// A 33-byte SEC1-compressed public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub bytes: [u8; 33],
+}
+
+/// A 32-byte x-only public key, as used by Taproot output keys (BIP340).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XOnlyPublicKey {
+    pub bytes: [u8; 32],
+}
+
+/// A 32-byte secp256k1 secret key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretKey {
+    pub bytes: [u8; 32],
+}
+
+impl SecretKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self { Self { bytes } }
+
+    /// Derives the corresponding public key via scalar multiplication of the
+    /// generator point.
+    pub fn public_key(&self) -> Result<PublicKey, BitcoinError> {
+        let scalar = BigUint::from_bytes_be(&self.bytes);
+        PublicKey::from_point(&scalar_mul(&scalar, &generator()))
+    }
+}
+
+impl PublicKey {
+    pub fn from_point(point: &CurvePoint) -> Result<Self, BitcoinError> {
+        match point {
+            CurvePoint::Infinity => Err(BitcoinError::ParseError(
+                "the point at infinity is not a valid public key".to_string(),
+            )),
+            CurvePoint::Affine { x, y } => {
+                let mut bytes = [0u8; 33];
+                bytes[0] = if is_odd(y) { 0x03 } else { 0x02 };
+                write_be(&mut bytes[1..], x);
+                Ok(Self { bytes })
+            },
+        }
+    }
+}
+
+/// Given a curve point, repeatedly adds the generator `G` until the
+/// resulting point has an even Y coordinate, as BIP341 requires of a
+/// Taproot output key. Returns the adjusted point and the number of
+/// additions performed (`0` if `point` was already even).
+pub fn make_even(point: CurvePoint) -> (CurvePoint, u64) {
+    let g = generator();
+    let mut current = point;
+    let mut additions = 0u64;
+
+    loop {
+        match &current {
+            CurvePoint::Affine { y, .. } if !is_odd(y) => return (current, additions),
+            _ => {
+                current = point_add(&current, &g);
+                additions += 1;
+            },
+        }
+    }
+}
+
+/// Extracts the 32-byte x coordinate of `point`, erroring on the point at
+/// infinity, which has none.
+pub fn x_only(point: &CurvePoint) -> Result<XOnlyPublicKey, BitcoinError> {
+    match point {
+        CurvePoint::Infinity => Err(BitcoinError::ParseError(
+            "the point at infinity has no x-only representation".to_string(),
+        )),
+        CurvePoint::Affine { x, .. } => {
+            let mut bytes = [0u8; 32];
+            write_be(&mut bytes, x);
+            Ok(XOnlyPublicKey { bytes })
+        },
+    }
+}
+
+/// Builds a version-1 (Taproot) witness program from an internal key: the
+/// key is normalized to even Y per BIP341 and its x-only coordinate becomes
+/// the output key. This does not apply the Merkle/script-path tweak
+/// (`Q = P + H_TapTweak(P)·G`); callers that need it are expected to tweak
+/// `internal_key` before calling this constructor.
+pub fn p2tr(internal_key: &CurvePoint) -> Result<WitnessProgram, BitcoinError> {
+    let (output_point, _) = make_even(internal_key.clone());
+    let output_key = x_only(&output_point)?;
+    Ok(WitnessProgram::new(1, output_key.bytes.to_vec()))
+}
+
+fn is_odd(n: &BigUint) -> bool { n.to_bytes_be().last().is_some_and(|byte| byte & 1 == 1) }
+
+fn write_be(dst: &mut [u8], n: &BigUint) {
+    let src = n.to_bytes_be();
+    let start = dst.len() - src.len();
+    dst[start..].copy_from_slice(&src);
+}
+
+/// The secp256k1 field prime `p = 2^256 - 2^32 - 977`.
+fn field_prime() -> BigUint {
+    (BigUint::one() << 256u32) - (BigUint::one() << 32u32) - BigUint::from(977u32)
+}
+
+/// The secp256k1 generator point `G`.
+fn generator() -> CurvePoint {
+    CurvePoint::Affine {
+        x: BigUint::parse_bytes(
+            b"79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            16,
+        )
+        .unwrap(),
+        y: BigUint::parse_bytes(
+            b"483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+            16,
+        )
+        .unwrap(),
+    }
+}
+
+fn mod_add(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint { (a + b) % p }
+
+fn mod_sub(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint { (a + p - (b % p)) % p }
+
+fn mod_mul(a: &BigUint, b: &BigUint, p: &BigUint) -> BigUint { (a * b) % p }
+
+fn mod_inverse(a: &BigUint, p: &BigUint) -> BigUint { a.modpow(&(p - BigUint::from(2u32)), p) }
+
+fn point_add(a: &CurvePoint, b: &CurvePoint) -> CurvePoint {
+    let (ax, ay) = match a {
+        CurvePoint::Infinity => return b.clone(),
+        CurvePoint::Affine { x, y } => (x, y),
+    };
+    let (bx, by) = match b {
+        CurvePoint::Infinity => return a.clone(),
+        CurvePoint::Affine { x, y } => (x, y),
+    };
+
+    let p = field_prime();
+
+    let lambda = if ax == bx {
+        if mod_add(ay, by, &p).is_zero() {
+            return CurvePoint::Infinity;
+        }
+        // Point doubling: lambda = 3x^2 / 2y
+        let num = mod_mul(&BigUint::from(3u32), &mod_mul(ax, ax, &p), &p);
+        let den = mod_inverse(&mod_add(ay, ay, &p), &p);
+        mod_mul(&num, &den, &p)
+    } else {
+        let num = mod_sub(by, ay, &p);
+        let den = mod_inverse(&mod_sub(bx, ax, &p), &p);
+        mod_mul(&num, &den, &p)
+    };
+
+    let x3 = mod_sub(&mod_sub(&mod_mul(&lambda, &lambda, &p), ax, &p), bx, &p);
+    let y3 = mod_sub(&mod_mul(&lambda, &mod_sub(ax, &x3, &p), &p), ay, &p);
+    CurvePoint::Affine { x: x3, y: y3 }
+}
+
+fn scalar_mul(scalar: &BigUint, point: &CurvePoint) -> CurvePoint {
+    let mut result = CurvePoint::Infinity;
+    let mut addend = point.clone();
+    let mut n = scalar.clone();
+
+    while !n.is_zero() {
+        if is_odd(&n) {
+            result = point_add(&result, &addend);
+        }
+        addend = point_add(&addend, &addend);
+        n >>= 1u32;
+    }
+
+    result
+}
@@ -0,0 +1,18 @@
+//! Segregated witness data structures shared by the transaction, keys and
+//! address modules.
+
+/// A per-input witness stack: one item per signature/script element required
+/// to spend a SegWit or Taproot output.
+pub type Witness = Vec<Vec<u8>>;
+
+/// A witness program: the version byte and committed program bytes embedded
+/// in a SegWit `scriptPubKey` or carried by a Bech32/Bech32m address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessProgram {
+    pub version: u8,
+    pub program: Vec<u8>,
+}
+
+impl WitnessProgram {
+    pub fn new(version: u8, program: Vec<u8>) -> Self { Self { version, program } }
+}
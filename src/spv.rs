@@ -0,0 +1,100 @@
+//! Lightweight SPV (simplified payment verification): block headers and
+//! merkle proofs that let a client confirm a transaction is included in a
+//! block without downloading the full block.
+
+use crate::hashes::sha256d;
+use crate::{BitcoinError, BitcoinSerialize};
+
+/// An 80-byte Bitcoin block header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub version:     i32,
+    pub prev_block:  [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub time:        u32,
+    pub bits:        u32,
+    pub nonce:       u32,
+}
+
+impl BlockHeader {
+    /// The block hash: a double-SHA256 of the 80-byte header, in internal
+    /// (non-reversed) byte order.
+    pub fn block_hash(&self) -> [u8; 32] { sha256d(&self.serialize()) }
+}
+
+impl BitcoinSerialize for BlockHeader {
+    fn serialize(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(80);
+        result.extend_from_slice(&self.version.to_le_bytes());
+        result.extend_from_slice(&self.prev_block);
+        result.extend_from_slice(&self.merkle_root);
+        result.extend_from_slice(&self.time.to_le_bytes());
+        result.extend_from_slice(&self.bits.to_le_bytes());
+        result.extend_from_slice(&self.nonce.to_le_bytes());
+        result
+    }
+}
+
+impl TryFrom<&[u8]> for BlockHeader {
+    type Error = BitcoinError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() < 80 {
+            return Err(BitcoinError::InvalidTransaction);
+        }
+
+        Ok(Self {
+            version:     i32::from_le_bytes(data[0..4].try_into().unwrap()),
+            prev_block:  data[4..36].try_into().unwrap(),
+            merkle_root: data[36..68].try_into().unwrap(),
+            time:        u32::from_le_bytes(data[68..72].try_into().unwrap()),
+            bits:        u32::from_le_bytes(data[72..76].try_into().unwrap()),
+            nonce:       u32::from_le_bytes(data[76..80].try_into().unwrap()),
+        })
+    }
+}
+
+/// A merkle proof that `txid` is included in the block a `BlockHeader`
+/// commits to.
+///
+/// Hashes here are in internal byte order (as produced by `sha256d`), not
+/// the reversed, display order block explorers use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub txid:     [u8; 32],
+    pub index:    u32,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// Recomputes the merkle root from `txid` and `siblings` and checks it
+    /// against `header.merkle_root`.
+    pub fn verify(&self, header: &BlockHeader) -> Result<bool, BitcoinError> {
+        // Each sibling consumes one bit of `index`; an index with bits set
+        // beyond that depth can't name a real leaf position. This also
+        // bounds `siblings.len()` below u32's bit width, so the shift below
+        // can't overflow.
+        if self.siblings.len() >= u32::BITS as usize
+            || self.index >> self.siblings.len() != 0
+        {
+            return Err(BitcoinError::InvalidTransaction);
+        }
+
+        let mut hash = self.txid;
+        let mut index = self.index;
+        for sibling in &self.siblings {
+            let mut data = Vec::with_capacity(64);
+            if index & 1 == 0 {
+                data.extend_from_slice(&hash);
+                data.extend_from_slice(sibling);
+            } else {
+                data.extend_from_slice(sibling);
+                data.extend_from_slice(&hash);
+            }
+            hash = sha256d(&data);
+            index >>= 1;
+        }
+
+        Ok(hash == header.merkle_root)
+    }
+}
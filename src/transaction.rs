@@ -0,0 +1,266 @@
+use crate::compact_size::{read_compact_size, write_compact_size};
+use crate::hashes::sha256d;
+use crate::segwit::Witness;
+use crate::{BitcoinError, BitcoinSerialize};
+
+// Legacy Bitcoin transaction
+#[derive(Debug, Clone)]
+pub struct LegacyTransaction {
+    pub version:   i32,
+    pub inputs:    Vec<TxInput>,
+    pub outputs:   Vec<TxOutput>,
+    pub lock_time: u32,
+}
+
+impl LegacyTransaction {
+    pub fn builder() -> LegacyTransactionBuilder { LegacyTransactionBuilder::new() }
+
+    /// The transaction id: a double-SHA256 over the non-witness
+    /// serialization, matching legacy txids even for SegWit transactions.
+    pub fn txid(&self) -> [u8; 32] { sha256d(&self.serialize_inner(false)) }
+
+    /// The witness transaction id: a double-SHA256 over the full SegWit
+    /// serialization. Identical to `txid()` when no input carries a witness.
+    pub fn wtxid(&self) -> [u8; 32] { sha256d(&self.serialize_inner(true)) }
+
+    fn has_witness(&self) -> bool { self.inputs.iter().any(|input| !input.witness.is_empty()) }
+
+    fn serialize_inner(&self, include_witness: bool) -> Vec<u8> {
+        let segwit = include_witness && self.has_witness();
+        let mut result = Vec::new();
+
+        result.extend_from_slice(&self.version.to_le_bytes());
+        if segwit {
+            result.push(0x00); // marker
+            result.push(0x01); // flag
+        }
+
+        write_compact_size(&mut result, self.inputs.len() as u64);
+        for input in &self.inputs {
+            result.extend_from_slice(&input.previous_output.txid);
+            result.extend_from_slice(&input.previous_output.vout.to_le_bytes());
+            write_compact_size(&mut result, input.script_sig.len() as u64);
+            result.extend_from_slice(&input.script_sig);
+            result.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+
+        write_compact_size(&mut result, self.outputs.len() as u64);
+        for output in &self.outputs {
+            result.extend_from_slice(&output.value.to_le_bytes());
+            write_compact_size(&mut result, output.script_pubkey.len() as u64);
+            result.extend_from_slice(&output.script_pubkey);
+        }
+
+        if segwit {
+            for input in &self.inputs {
+                write_compact_size(&mut result, input.witness.len() as u64);
+                for item in &input.witness {
+                    write_compact_size(&mut result, item.len() as u64);
+                    result.extend_from_slice(item);
+                }
+            }
+        }
+
+        result.extend_from_slice(&self.lock_time.to_le_bytes());
+        result
+    }
+}
+
+// Transaction builder
+pub struct LegacyTransactionBuilder {
+    pub version:   i32,
+    pub inputs:    Vec<TxInput>,
+    pub outputs:   Vec<TxOutput>,
+    pub lock_time: u32,
+}
+
+impl Default for LegacyTransactionBuilder {
+    fn default() -> Self {
+        Self {
+            version:   1,
+            inputs:    Vec::with_capacity(1),
+            outputs:   Vec::with_capacity(0),
+            lock_time: 0,
+        }
+    }
+}
+
+impl LegacyTransactionBuilder {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn version(mut self, version: i32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn add_input(mut self, input: TxInput) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    pub fn add_output(mut self, output: TxOutput) -> Self {
+        self.outputs.push(output);
+        self
+    }
+
+    pub fn lock_time(mut self, lock_time: u32) -> Self {
+        self.lock_time = lock_time;
+        self
+    }
+
+    pub fn build(self) -> LegacyTransaction {
+        LegacyTransaction {
+            version:   self.version,
+            inputs:    self.inputs,
+            outputs:   self.outputs,
+            lock_time: self.lock_time,
+        }
+    }
+}
+
+// Transaction components
+#[derive(Debug, Clone)]
+pub struct TxInput {
+    pub previous_output: OutPoint,
+    pub script_sig:      Vec<u8>,
+    pub sequence:        u32,
+    /// The SegWit witness stack for this input; empty for legacy inputs.
+    pub witness:         Witness,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOutput {
+    pub value:         u64, // in satoshis
+    pub script_pubkey: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutPoint {
+    pub txid: [u8; 32],
+    pub vout: u32,
+}
+
+// Decoding legacy transaction
+impl TryFrom<&[u8]> for LegacyTransaction {
+    type Error = BitcoinError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        // The fixed-size fields take 8 bytes (4 version + 4 lock_time);
+        // everything in between is variable-length and bounds-checked as
+        // it's read.
+        if data.len() < 8 {
+            return Err(BitcoinError::InvalidTransaction);
+        }
+
+        let version = read_i32_le(data, 0)?;
+        let mut offset = 4;
+
+        // BIP144: a 0x00 marker followed by a non-zero flag byte signals the
+        // SegWit serialization. This mirrors a known consensus ambiguity with
+        // legacy, zero-input transactions, which we don't attempt to parse.
+        let segwit = data.get(offset) == Some(&0x00) && data.get(offset + 1).is_some_and(|&f| f != 0);
+        if segwit {
+            offset += 2;
+        }
+
+        let (input_count, consumed) = read_compact_size(data, offset)?;
+        offset += consumed;
+
+        let mut inputs = Vec::with_capacity(input_count as usize);
+        for _ in 0..input_count {
+            let txid = read_bytes32(data, offset)?;
+            offset += 32;
+
+            let vout = read_u32_le(data, offset)?;
+            offset += 4;
+
+            let (script_len, consumed) = read_compact_size(data, offset)?;
+            offset += consumed;
+
+            let script_sig = read_slice(data, offset, script_len as usize)?.to_vec();
+            offset += script_len as usize;
+
+            let sequence = read_u32_le(data, offset)?;
+            offset += 4;
+
+            inputs.push(TxInput {
+                previous_output: OutPoint { txid, vout },
+                script_sig,
+                sequence,
+                witness: Vec::new(),
+            });
+        }
+
+        let (output_count, consumed) = read_compact_size(data, offset)?;
+        offset += consumed;
+
+        let mut outputs = Vec::with_capacity(output_count as usize);
+        for _ in 0..output_count {
+            let value = read_u64_le(data, offset)?;
+            offset += 8;
+
+            let (script_len, consumed) = read_compact_size(data, offset)?;
+            offset += consumed;
+
+            let script_pubkey = read_slice(data, offset, script_len as usize)?.to_vec();
+            offset += script_len as usize;
+
+            outputs.push(TxOutput { value, script_pubkey });
+        }
+
+        if segwit {
+            for input in &mut inputs {
+                let (stack_count, consumed) = read_compact_size(data, offset)?;
+                offset += consumed;
+
+                let mut witness = Vec::with_capacity(stack_count as usize);
+                for _ in 0..stack_count {
+                    let (item_len, consumed) = read_compact_size(data, offset)?;
+                    offset += consumed;
+
+                    let item = read_slice(data, offset, item_len as usize)?.to_vec();
+                    offset += item_len as usize;
+
+                    witness.push(item);
+                }
+                input.witness = witness;
+            }
+        }
+
+        let lock_time = read_u32_le(data, offset)?;
+
+        Ok(Self {
+            version,
+            inputs,
+            outputs,
+            lock_time,
+        })
+    }
+}
+
+// Custom serialization for transaction
+impl BitcoinSerialize for LegacyTransaction {
+    fn serialize(&self) -> Vec<u8> { self.serialize_inner(true) }
+}
+
+fn read_u32_le(data: &[u8], offset: usize) -> Result<u32, BitcoinError> {
+    Ok(u32::from_le_bytes(read_slice(data, offset, 4)?.try_into().unwrap()))
+}
+
+fn read_u64_le(data: &[u8], offset: usize) -> Result<u64, BitcoinError> {
+    Ok(u64::from_le_bytes(read_slice(data, offset, 8)?.try_into().unwrap()))
+}
+
+// Helper function to read i32 from bytes in little-endian format
+fn read_i32_le(data: &[u8], offset: usize) -> Result<i32, BitcoinError> {
+    Ok(i32::from_le_bytes(read_slice(data, offset, 4)?.try_into().unwrap()))
+}
+
+fn read_bytes32(data: &[u8], offset: usize) -> Result<[u8; 32], BitcoinError> {
+    Ok(read_slice(data, offset, 32)?.try_into().unwrap())
+}
+
+fn read_slice(data: &[u8], offset: usize, len: usize) -> Result<&[u8], BitcoinError> {
+    let end = offset.checked_add(len).ok_or(BitcoinError::InvalidTransaction)?;
+    data.get(offset..end).ok_or(BitcoinError::InvalidTransaction)
+}